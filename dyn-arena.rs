@@ -0,0 +1,821 @@
+// Nightly-only: `#![feature(ptr_metadata)]` is inherited from
+// dyn-query-interface.rs's `interfaces!`/`Val::query_ref`/`query_mut`
+// machinery, carried forward here for API continuity even though the arena
+// allocator itself needs nothing unstable. Build with
+// `rustup run nightly rustc --edition 2021 dyn-arena.rs`.
+#![feature(ptr_metadata)]
+
+use std::alloc::{self, Layout};
+use std::any::{Any, TypeId};
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+type BoxedObj = Box<dyn Obj>;
+
+// AnyBoxedObj -----------------------------------------------------------------
+
+trait AnyBoxedObj {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T> AnyBoxedObj for T
+where
+    T: 'static + Obj,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+// PartialEqBoxedObj -----------------------------------------------------------
+
+trait PartialEqBoxedObj {
+    fn dyn_eq(&self, other: &dyn Obj) -> bool;
+}
+
+impl<T> PartialEqBoxedObj for T
+where
+    T: 'static + Obj + PartialEq,
+{
+    fn dyn_eq(&self, other: &dyn Obj) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.eq(other)
+        } else {
+            false
+        }
+    }
+}
+
+// PartialOrdBoxedObj ----------------------------------------------------------
+
+trait PartialOrdBoxedObj {
+    fn dyn_partial_cmp(&self, other: &dyn Obj) -> Option<std::cmp::Ordering>;
+}
+
+impl<T> PartialOrdBoxedObj for T
+where
+    T: 'static + Obj + PartialEq + PartialOrd,
+{
+    fn dyn_partial_cmp(&self, other: &dyn Obj) -> Option<std::cmp::Ordering> {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.partial_cmp(other)
+        } else {
+            None
+        }
+    }
+}
+
+// HashBoxedObj ------------------------------------------------------------------
+
+/// Mirrors `PartialEqBoxedObj`'s erasure pattern for `Hash`. The `TypeId` is
+/// fed into the hasher ahead of the object's own fields so that two distinct
+/// `Obj` types whose fields happen to hash identically don't collapse into
+/// the same bucket, matching the fact that `dyn_eq` already treats them as
+/// unequal.
+trait HashBoxedObj {
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T> HashBoxedObj for T
+where
+    T: 'static + Obj + Hash,
+{
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+// CloneBoxedObj -----------------------------------------------------------------
+
+/// Produces an independent, heap-allocated copy of the wrapped object,
+/// giving callers an explicit choice between reference-sharing (`Rc::clone`)
+/// and by-value duplication (`Val::deep_clone`).
+trait CloneBoxedObj {
+    fn dyn_clone(&self) -> BoxedObj;
+}
+
+impl<T> CloneBoxedObj for T
+where
+    T: 'static + Obj + Clone,
+{
+    fn dyn_clone(&self) -> BoxedObj {
+        Box::new(self.clone())
+    }
+}
+
+// ObjectBoxedObj ----------------------------------------------------------------
+
+/// A type-erased pointer into a registered trait object, carried alongside
+/// the vtable metadata that produced it. `interfaces!` is the only producer
+/// of a `Query`, which is what lets `Val::query_ref`/`query_mut` assume the
+/// metadata always matches the trait whose `TypeId` was asked for.
+struct Query {
+    data: *const (),
+    metadata: *const (),
+}
+
+trait ObjectBoxedObj {
+    /// Look up a trait coercion for `target` registered via `interfaces!`.
+    fn dyn_query(&self, target: TypeId) -> Option<Query>;
+    /// Mutable counterpart of [`ObjectBoxedObj::dyn_query`].
+    fn dyn_query_mut(&mut self, target: TypeId) -> Option<Query>;
+}
+
+/// Registers trait-object coercions for a concrete `Obj` type, so a `Val`
+/// can later recover `&dyn Trait`/`&mut dyn Trait` for any of the listed
+/// traits via [`Val::query_ref`]/[`Val::query_mut`]. This macro is the only
+/// place allowed to build a `Query`, since it is the only place that can
+/// guarantee the `TypeId` queried for and the vtable metadata stashed into
+/// it agree with each other.
+macro_rules! interfaces {
+    ($ty:ty : $($trait:ty),* $(,)?) => {
+        impl ObjectBoxedObj for $ty {
+            fn dyn_query(&self, target: TypeId) -> Option<Query> {
+                $(
+                    if target == TypeId::of::<$trait>() {
+                        let obj: &$trait = self;
+                        let (data, metadata) = (obj as *const $trait).to_raw_parts();
+                        return Some(Query {
+                            data: data as *const (),
+                            // SAFETY: `metadata` was produced from `&$trait`, so
+                            // reinterpreting it as `$trait`'s own metadata type
+                            // further down in `query_ref`/`query_mut` is sound.
+                            metadata: unsafe { std::mem::transmute_copy(&metadata) },
+                        });
+                    }
+                )*
+                let _ = target;
+                None
+            }
+
+            fn dyn_query_mut(&mut self, target: TypeId) -> Option<Query> {
+                $(
+                    if target == TypeId::of::<$trait>() {
+                        let obj: &mut $trait = self;
+                        let (data, metadata) = (obj as *mut $trait).to_raw_parts();
+                        return Some(Query {
+                            data: data as *const (),
+                            metadata: unsafe { std::mem::transmute_copy(&metadata) },
+                        });
+                    }
+                )*
+                let _ = target;
+                None
+            }
+        }
+    };
+}
+
+// ops ---------------------------------------------------------------------------
+
+/// Runtime arithmetic dispatch across heterogeneous `Obj` types, the way a
+/// tree-walking interpreter needs for `val_a + val_b`. Each method defaults
+/// to "unsupported"; concrete `Obj` impls override the operators they
+/// understand, downcasting `rhs` to decide which coercions to allow. Results
+/// are always freshly heap-allocated, so they carry no arena lifetime.
+mod ops {
+    use super::{Obj, Val};
+
+    pub trait BinOp {
+        fn add(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+            let _ = rhs;
+            None
+        }
+
+        fn sub(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+            let _ = rhs;
+            None
+        }
+
+        fn mul(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+            let _ = rhs;
+            None
+        }
+    }
+
+    impl<'a, 'b> std::ops::Add<&Val<'b>> for &Val<'a> {
+        type Output = Val<'static>;
+
+        fn add(self, rhs: &Val<'b>) -> Val<'static> {
+            self.with_obj(|lhs| rhs.with_obj(|rhs| lhs.add(rhs)))
+                .unwrap_or_else(|| panic!("unsupported operands for +: {:?} and {:?}", self, rhs))
+        }
+    }
+
+    impl<'a, 'b> std::ops::Sub<&Val<'b>> for &Val<'a> {
+        type Output = Val<'static>;
+
+        fn sub(self, rhs: &Val<'b>) -> Val<'static> {
+            self.with_obj(|lhs| rhs.with_obj(|rhs| lhs.sub(rhs)))
+                .unwrap_or_else(|| panic!("unsupported operands for -: {:?} and {:?}", self, rhs))
+        }
+    }
+
+    impl<'a, 'b> std::ops::Mul<&Val<'b>> for &Val<'a> {
+        type Output = Val<'static>;
+
+        fn mul(self, rhs: &Val<'b>) -> Val<'static> {
+            self.with_obj(|lhs| rhs.with_obj(|rhs| lhs.mul(rhs)))
+                .unwrap_or_else(|| panic!("unsupported operands for *: {:?} and {:?}", self, rhs))
+        }
+    }
+}
+
+// Obj -------------------------------------------------------------------------
+
+trait Obj:
+    AnyBoxedObj
+    + PartialEqBoxedObj
+    + PartialOrdBoxedObj
+    + HashBoxedObj
+    + CloneBoxedObj
+    + ObjectBoxedObj
+    + ops::BinOp
+    + Any
+    + std::fmt::Debug
+{
+}
+
+// ArenaBorrow / ArenaBorrowMut ---------------------------------------------------
+
+/// Runtime borrow tracking for an arena-allocated object, mirroring
+/// `RefCell`'s `BorrowFlag`: `0` means unborrowed, a positive count is that
+/// many live shared borrows, `-1` means a live exclusive borrow. Without
+/// this, `Val::object_mut`/`query_mut` could hand out a `&mut` into arena
+/// memory while another `&`/`&mut` into the same object was still live,
+/// since the arena (unlike `Rc<RefCell<_>>`) has no borrow checking of its
+/// own.
+fn arena_borrow<'g>(flag: &'g Cell<isize>) -> ArenaBorrow<'g> {
+    let state = flag.get();
+    if state < 0 {
+        panic!("already mutably borrowed");
+    }
+    flag.set(state + 1);
+    ArenaBorrow { flag }
+}
+
+fn arena_borrow_mut<'g>(flag: &'g Cell<isize>) -> ArenaBorrowMut<'g> {
+    if flag.get() != 0 {
+        panic!("already borrowed");
+    }
+    flag.set(-1);
+    ArenaBorrowMut { flag }
+}
+
+struct ArenaBorrow<'g> {
+    flag: &'g Cell<isize>,
+}
+
+impl<'g> Drop for ArenaBorrow<'g> {
+    fn drop(&mut self) {
+        self.flag.set(self.flag.get() - 1);
+    }
+}
+
+struct ArenaBorrowMut<'g> {
+    flag: &'g Cell<isize>,
+}
+
+impl<'g> Drop for ArenaBorrowMut<'g> {
+    fn drop(&mut self) {
+        self.flag.set(0);
+    }
+}
+
+// ObjRef / ObjRefMut ------------------------------------------------------------
+
+/// A borrowed `Obj` reference, however it is owned: a `RefCell`-tracked
+/// borrow for `Rc`-backed `Val`s, or a plain reference into arena memory
+/// guarded by an [`ArenaBorrow`] for arena-backed ones. Lets
+/// [`Val::object`]/[`Val::query_ref`] hand back one type regardless of which
+/// backend produced it.
+enum ObjRef<'g, T: ?Sized> {
+    Rc(Ref<'g, T>),
+    Arena(&'g T, ArenaBorrow<'g>),
+}
+
+impl<'g, T: ?Sized> Deref for ObjRef<'g, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            ObjRef::Rc(r) => r,
+            ObjRef::Arena(r, _) => r,
+        }
+    }
+}
+
+/// Mutable counterpart of [`ObjRef`].
+enum ObjRefMut<'g, T: ?Sized> {
+    Rc(RefMut<'g, T>),
+    Arena(&'g mut T, ArenaBorrowMut<'g>),
+}
+
+impl<'g, T: ?Sized> Deref for ObjRefMut<'g, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            ObjRefMut::Rc(r) => r,
+            ObjRefMut::Arena(r, _) => r,
+        }
+    }
+}
+
+impl<'g, T: ?Sized> DerefMut for ObjRefMut<'g, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            ObjRefMut::Rc(r) => r,
+            ObjRefMut::Arena(r, _) => r,
+        }
+    }
+}
+
+// Val -------------------------------------------------------------------------
+
+/// Either an `Rc`-shared, heap-allocated object (the original design, no
+/// lifetime constraints), or a pointer into a [`ValArena`]'s bump-allocated
+/// memory (tied to the arena's lifetime `'a`), paired with its
+/// [`ArenaBorrow`]/[`ArenaBorrowMut`] flag. Both interoperate in
+/// comparisons, hashing, downcasts and arithmetic.
+enum Val<'a> {
+    Rc(Rc<RefCell<BoxedObj>>),
+    Arena(*mut dyn Obj, *const Cell<isize>, PhantomData<&'a ()>),
+}
+
+impl<'a, 'b> PartialEq<Val<'b>> for Val<'a> {
+    fn eq(&self, other: &Val<'b>) -> bool {
+        self.with_obj(|a| other.with_obj(|b| a.dyn_eq(b)))
+    }
+}
+
+impl<'a> Eq for Val<'a> {}
+
+impl<'a, 'b> PartialOrd<Val<'b>> for Val<'a> {
+    fn partial_cmp(&self, other: &Val<'b>) -> Option<std::cmp::Ordering> {
+        self.with_obj(|a| other.with_obj(|b| a.dyn_partial_cmp(b)))
+    }
+}
+
+impl<'a> Hash for Val<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.with_obj(|o| o.dyn_hash(state))
+    }
+}
+
+impl<'a> std::fmt::Debug for Val<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with_obj(|o| std::fmt::Debug::fmt(o, f))
+    }
+}
+
+impl Val<'static> {
+    pub fn new(value: BoxedObj) -> Self {
+        Val::Rc(Rc::new(RefCell::new(value)))
+    }
+}
+
+impl<'a> Val<'a> {
+    /// Run `f` with a `&dyn Obj` borrowed from whichever backend this `Val`
+    /// uses. Internal helper shared by comparisons, hashing, `Debug` and
+    /// arithmetic.
+    fn with_obj<R>(&self, f: impl FnOnce(&dyn Obj) -> R) -> R {
+        match self {
+            Val::Rc(rc) => f(&**rc.borrow()),
+            // SAFETY: the pointer was written by `ValArena::alloc` and the
+            // `PhantomData<&'a ()>` ties this `Val` to the arena's lifetime,
+            // so the pointee is still alive.
+            Val::Arena(ptr, flag, _) => {
+                let _borrow = arena_borrow(unsafe { &**flag });
+                f(unsafe { &**ptr })
+            }
+        }
+    }
+
+    /// Return reference to object of type T.
+    pub fn object<T: Any>(&self) -> Option<ObjRef<'_, T>> {
+        match self {
+            Val::Rc(rc) => {
+                let value = rc.borrow();
+                if value.as_any().is::<T>() {
+                    Some(ObjRef::Rc(Ref::map(value, |obj| {
+                        obj.as_any().downcast_ref::<T>().unwrap()
+                    })))
+                } else {
+                    None
+                }
+            }
+            Val::Arena(ptr, flag, _) => {
+                let borrow = arena_borrow(unsafe { &**flag });
+                let obj: &dyn Obj = unsafe { &**ptr };
+                obj.as_any()
+                    .downcast_ref::<T>()
+                    .map(|r| ObjRef::Arena(r, borrow))
+            }
+        }
+    }
+
+    /// Return mutable reference to object of type T.
+    pub fn object_mut<T: Any>(&self) -> Option<ObjRefMut<'_, T>> {
+        match self {
+            Val::Rc(rc) => {
+                let value = rc.borrow_mut();
+                if value.as_any().is::<T>() {
+                    Some(ObjRefMut::Rc(RefMut::map(value, |obj| {
+                        obj.as_any_mut().downcast_mut::<T>().unwrap()
+                    })))
+                } else {
+                    None
+                }
+            }
+            Val::Arena(ptr, flag, _) => {
+                let borrow = arena_borrow_mut(unsafe { &**flag });
+                let obj: &mut dyn Obj = unsafe { &mut **ptr };
+                obj.as_any_mut()
+                    .downcast_mut::<T>()
+                    .map(|r| ObjRefMut::Arena(r, borrow))
+            }
+        }
+    }
+
+    /// Return the object of type T, consuming the val. Arena-backed `Val`s
+    /// cannot be moved out of (the arena still owns and will drop them), so
+    /// this falls back to `deep_clone`-ing them out instead.
+    pub fn into_object<T: Any>(self) -> Option<T> {
+        match self {
+            Val::Rc(rc) => {
+                let value = Rc::try_unwrap(rc).ok()?.into_inner();
+                value.into_any().downcast::<T>().ok().map(|inner| *inner)
+            }
+            Val::Arena(ptr, flag, _) => {
+                let borrow = arena_borrow(unsafe { &*flag });
+                let cloned = unsafe { &*ptr }.dyn_clone();
+                drop(borrow);
+                cloned.into_any().downcast::<T>().ok().map(|inner| *inner)
+            }
+        }
+    }
+
+    /// Ask the inner object whether it implements trait `U` (registered via
+    /// `interfaces!`), returning a `&dyn U` if so.
+    pub fn query_ref<U: ?Sized + 'static>(&self) -> Option<ObjRef<'_, U>> {
+        match self {
+            Val::Rc(rc) => {
+                let value = rc.borrow();
+                let query = value.dyn_query(TypeId::of::<U>())?;
+                let metadata: <U as std::ptr::Pointee>::Metadata =
+                    unsafe { std::mem::transmute_copy(&query.metadata) };
+
+                Some(ObjRef::Rc(Ref::map(value, |_| unsafe {
+                    &*std::ptr::from_raw_parts::<U>(query.data, metadata)
+                })))
+            }
+            Val::Arena(ptr, flag, _) => {
+                let borrow = arena_borrow(unsafe { &**flag });
+                let obj: &dyn Obj = unsafe { &**ptr };
+                let query = obj.dyn_query(TypeId::of::<U>())?;
+                let metadata: <U as std::ptr::Pointee>::Metadata =
+                    unsafe { std::mem::transmute_copy(&query.metadata) };
+
+                Some(ObjRef::Arena(
+                    unsafe { &*std::ptr::from_raw_parts::<U>(query.data, metadata) },
+                    borrow,
+                ))
+            }
+        }
+    }
+
+    /// Mutable counterpart of [`Val::query_ref`].
+    pub fn query_mut<U: ?Sized + 'static>(&self) -> Option<ObjRefMut<'_, U>> {
+        match self {
+            Val::Rc(rc) => {
+                let mut value = rc.borrow_mut();
+                let query = value.dyn_query_mut(TypeId::of::<U>())?;
+                let metadata: <U as std::ptr::Pointee>::Metadata =
+                    unsafe { std::mem::transmute_copy(&query.metadata) };
+
+                Some(ObjRefMut::Rc(RefMut::map(value, |_| unsafe {
+                    &mut *std::ptr::from_raw_parts_mut::<U>(query.data as *mut (), metadata)
+                })))
+            }
+            Val::Arena(ptr, flag, _) => {
+                let borrow = arena_borrow_mut(unsafe { &**flag });
+                let obj: &mut dyn Obj = unsafe { &mut **ptr };
+                let query = obj.dyn_query_mut(TypeId::of::<U>())?;
+                let metadata: <U as std::ptr::Pointee>::Metadata =
+                    unsafe { std::mem::transmute_copy(&query.metadata) };
+
+                Some(ObjRefMut::Arena(
+                    unsafe { &mut *std::ptr::from_raw_parts_mut::<U>(query.data as *mut (), metadata) },
+                    borrow,
+                ))
+            }
+        }
+    }
+
+    /// Return an independent copy of this `Val`, always `Rc`-backed so the
+    /// result carries no arena lifetime, rather than a new handle onto the
+    /// same shared or arena-owned object.
+    pub fn deep_clone(&self) -> Val<'static> {
+        Val::new(self.with_obj(|obj| obj.dyn_clone()))
+    }
+}
+
+// ValArena ----------------------------------------------------------------------
+
+const CHUNK_SIZE: usize = 4096;
+
+struct Chunk {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Self {
+        let layout = Layout::from_size_align(size.max(1), 16).expect("valid chunk layout");
+        let ptr = unsafe { alloc::alloc(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Chunk { ptr, layout }
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+/// A grow-only bump allocator for `Val` objects. Each allocation just bumps
+/// an offset pointer and placement-writes the `Obj` in place, avoiding the
+/// per-object `Box`/`Rc` heap traffic that dominates interpreter workloads
+/// churning through millions of short-lived `Val`s. Every live object is
+/// dropped in place, and the whole arena is freed at once, when the
+/// `ValArena` itself is dropped. Arena-backed `Val`s borrow the arena's
+/// lifetime, so they cannot outlive it.
+struct ValArena {
+    chunks: RefCell<Vec<Chunk>>,
+    offset: Cell<usize>,
+    live: RefCell<Vec<*mut dyn Obj>>,
+}
+
+impl ValArena {
+    pub fn new() -> Self {
+        ValArena {
+            chunks: RefCell::new(vec![Chunk::new(CHUNK_SIZE)]),
+            offset: Cell::new(0),
+            live: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Allocate `obj` inside the arena and return a `Val` borrowing it, along
+    /// with its own borrow-tracking flag (also arena-allocated, so arena
+    /// `Val`s stay borrow-checked without an `Rc`/`Box` per object).
+    pub fn alloc<T: Obj>(&self, obj: T) -> Val<'_> {
+        let flag_ptr = self.bump(Layout::new::<Cell<isize>>()) as *mut Cell<isize>;
+        unsafe {
+            flag_ptr.write(Cell::new(0));
+        }
+
+        let layout = Layout::new::<T>();
+        let ptr = self.bump(layout) as *mut T;
+        unsafe {
+            ptr.write(obj);
+        }
+
+        let fat: *mut dyn Obj = ptr;
+        self.live.borrow_mut().push(fat);
+        Val::Arena(fat, flag_ptr as *const Cell<isize>, PhantomData)
+    }
+
+    fn bump(&self, layout: Layout) -> *mut u8 {
+        let mut chunks = self.chunks.borrow_mut();
+        let base = chunks.last().unwrap().ptr.as_ptr() as usize;
+        let chunk_size = chunks.last().unwrap().layout.size();
+        let cur = base + self.offset.get();
+        let aligned = (cur + layout.align() - 1) & !(layout.align() - 1);
+
+        if aligned + layout.size() > base + chunk_size {
+            let new_size = CHUNK_SIZE.max(layout.size() + layout.align());
+            chunks.push(Chunk::new(new_size));
+            self.offset.set(0);
+            drop(chunks);
+            return self.bump(layout);
+        }
+
+        self.offset.set(aligned + layout.size() - base);
+        aligned as *mut u8
+    }
+}
+
+impl Drop for ValArena {
+    fn drop(&mut self) {
+        for ptr in self.live.borrow_mut().drain(..) {
+            unsafe { std::ptr::drop_in_place(ptr) };
+        }
+    }
+}
+
+// Obj Int ---------------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone)]
+struct Int {
+    int: i64,
+}
+
+impl Obj for Int {}
+
+interfaces!(Int:);
+
+impl ops::BinOp for Int {
+    fn add(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int + i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int + c.x, y: c.y }))
+        } else {
+            None
+        }
+    }
+
+    fn sub(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int - i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int - c.x, y: -c.y }))
+        } else {
+            None
+        }
+    }
+
+    fn mul(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int * i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int * c.x, y: self.int * c.y }))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<i64> for Val<'static> {
+    fn from(int: i64) -> Self {
+        Val::new(Box::new(Int { int }))
+    }
+}
+
+// Obj Complex -----------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone)]
+struct Complex {
+    x: i64,
+    y: i64,
+}
+
+impl Obj for Complex {}
+
+interfaces!(Complex: dyn Display);
+
+impl ops::BinOp for Complex {
+    fn add(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.x + c.x, y: self.y + c.y }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x + i.int, y: self.y }))
+        } else {
+            None
+        }
+    }
+
+    fn sub(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.x - c.x, y: self.y - c.y }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x - i.int, y: self.y }))
+        } else {
+            None
+        }
+    }
+
+    fn mul(&self, rhs: &dyn Obj) -> Option<Val<'static>> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            // (x1 + y1*i) * (x2 + y2*i) = (x1*x2 - y1*y2) + (x1*y2 + y1*x2)*i
+            Some(Val::from(Complex {
+                x: self.x * c.x - self.y * c.y,
+                y: self.x * c.y + self.y * c.x,
+            }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x * i.int, y: self.y * i.int }))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}+{}i", self.x, self.y)
+    }
+}
+
+impl From<Complex> for Val<'static> {
+    fn from(value: Complex) -> Self {
+        Val::new(Box::new(value))
+    }
+}
+
+// main ------------------------------------------------------------------------
+
+fn main() {
+    // Create an int
+    let ival = Val::from(1337);
+    println!("ival = {:?}", ival);
+
+    // Create the complex
+    let complex = Complex { x: 23, y: 42 };
+    println!("complex = {:?}", complex);
+
+    // Turn complex into a val
+    let val = Val::from(complex);
+    println!("val = {:?}", val);
+
+    // Use Vals as dictionary keys, the way a scripting runtime would.
+    let mut dict: HashMap<Val<'static>, &str> = HashMap::new();
+    dict.insert(Val::from(1337), "lucky number");
+    dict.insert(Val::from(Complex { x: 23, y: 42 }), "a complex one");
+
+    println!("dict[ival] = {:?}", dict.get(&ival));
+    println!("dict[val] = {:?}", dict.get(&val));
+    println!("dict[Val::from(7)] = {:?}", dict.get(&Val::from(7)));
+
+    // `Rc::clone`-alike: a shared handle onto the same object.
+    let shared = match &val {
+        Val::Rc(rc) => Val::Rc(Rc::clone(rc)),
+        Val::Arena(..) => unreachable!(),
+    };
+
+    // `deep_clone`: an independent copy of the object.
+    let copy = val.deep_clone();
+
+    val.object_mut::<Complex>().unwrap().x = 9999;
+    println!("shared sees the mutation = {:?}", &*shared.object::<Complex>().unwrap());
+    println!("copy does not = {:?}", &*copy.object::<Complex>().unwrap());
+
+    // Dynamic arithmetic across heterogeneous Obj types.
+    let a = Val::from(Complex { x: 1, y: 2 });
+    let b = Val::from(Complex { x: 3, y: 4 });
+    let i = Val::from(10);
+
+    println!("a + b = {:?}", &a + &b);
+    println!("a - b = {:?}", &a - &b);
+    println!("a * b = {:?}", &a * &b);
+    println!("a + i = {:?}", &a + &i);
+    println!("i * a = {:?}", &i * &a);
+    println!("i + i = {:?}", &i + &i);
+
+    // Query the sum for `dyn Display`, registered via `interfaces!`.
+    let sum = &a + &b;
+    println!("a + b as dyn Display = {}", &*sum.query_ref::<dyn Display>().unwrap());
+
+    // Consume the sum, extracting its Complex by value.
+    println!("sum.into_object::<Complex>() = {:?}", sum.into_object::<Complex>());
+
+    // Arena-backed allocation: millions of short-lived Vals without
+    // individual Box/Rc heap traffic, all freed in one shot when `arena`
+    // drops.
+    let arena = ValArena::new();
+    let arena_vals: Vec<Val> = (0..100_000)
+        .map(|n| arena.alloc(Int { int: n }))
+        .collect();
+
+    println!("arena_vals[0] = {:?}", arena_vals[0]);
+    println!("arena_vals[99999] = {:?}", arena_vals[99_999]);
+
+    // Arena-allocated and Rc-allocated Vals interoperate in comparisons,
+    // downcasts and arithmetic.
+    println!(
+        "arena_vals[1337] == ival = {}",
+        arena_vals[1337] == ival
+    );
+    println!("arena_vals[2] + arena_vals[3] = {:?}", &arena_vals[2] + &arena_vals[3]);
+    println!("arena_vals[5] + i = {:?}", &arena_vals[5] + &i);
+    println!(
+        "arena_vals[7].object::<Int>() = {:?}",
+        &*arena_vals[7].object::<Int>().unwrap()
+    );
+}
@@ -0,0 +1,427 @@
+use std::any::{Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+type BoxedObj = Box<dyn Obj>;
+
+// AnyBoxedObj -----------------------------------------------------------------
+
+trait AnyBoxedObj {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T> AnyBoxedObj for T
+where
+    T: 'static + Obj,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+// PartialEqBoxedObj -----------------------------------------------------------
+
+trait PartialEqBoxedObj {
+    fn dyn_eq(&self, other: &BoxedObj) -> bool;
+}
+
+impl<T> PartialEqBoxedObj for T
+where
+    T: 'static + Obj + PartialEq,
+{
+    fn dyn_eq(&self, other: &BoxedObj) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.eq(other)
+        } else {
+            false
+        }
+    }
+}
+
+impl PartialEq for BoxedObj {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+// fix for `move occurs because `*__arg_1_0` has type `Box<dyn Obj>`, which does not implement the `Copy` trait`
+// https://github.com/rust-lang/rust/issues/31740#issuecomment-700950186
+impl PartialEq<&Self> for BoxedObj {
+    fn eq(&self, other: &&Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+impl Eq for BoxedObj {}
+
+// PartialOrdBoxedObj ----------------------------------------------------------
+
+trait PartialOrdBoxedObj {
+    fn dyn_partial_cmp(&self, other: &BoxedObj) -> Option<std::cmp::Ordering>;
+}
+
+impl<T> PartialOrdBoxedObj for T
+where
+    T: 'static + Obj + PartialEq + PartialOrd,
+{
+    fn dyn_partial_cmp(&self, other: &BoxedObj) -> Option<std::cmp::Ordering> {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.partial_cmp(other)
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialOrd for BoxedObj {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.dyn_partial_cmp(other)
+    }
+}
+
+// HashBoxedObj ------------------------------------------------------------------
+
+/// Mirrors `PartialEqBoxedObj`'s erasure pattern for `Hash`. The `TypeId` is
+/// fed into the hasher ahead of the object's own fields so that two distinct
+/// `Obj` types whose fields happen to hash identically don't collapse into
+/// the same bucket, matching the fact that `dyn_eq` already treats them as
+/// unequal.
+trait HashBoxedObj {
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T> HashBoxedObj for T
+where
+    T: 'static + Obj + Hash,
+{
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+impl Hash for BoxedObj {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state)
+    }
+}
+
+// CloneBoxedObj -----------------------------------------------------------------
+
+/// Produces an independent, heap-allocated copy of the wrapped object,
+/// giving callers an explicit choice between reference-sharing (`Rc::clone`)
+/// and by-value duplication (`Val::deep_clone`).
+trait CloneBoxedObj {
+    fn dyn_clone(&self) -> BoxedObj;
+}
+
+impl<T> CloneBoxedObj for T
+where
+    T: 'static + Obj + Clone,
+{
+    fn dyn_clone(&self) -> BoxedObj {
+        Box::new(self.clone())
+    }
+}
+
+// ops ---------------------------------------------------------------------------
+
+/// Runtime arithmetic dispatch across heterogeneous `Obj` types, the way a
+/// tree-walking interpreter needs for `val_a + val_b`. Each method defaults
+/// to "unsupported"; concrete `Obj` impls override the operators they
+/// understand, downcasting `rhs` to decide which coercions to allow.
+mod ops {
+    use super::{BoxedObj, Val};
+
+    pub trait BinOp {
+        fn add(&self, rhs: &BoxedObj) -> Option<Val> {
+            let _ = rhs;
+            None
+        }
+
+        fn sub(&self, rhs: &BoxedObj) -> Option<Val> {
+            let _ = rhs;
+            None
+        }
+
+        fn mul(&self, rhs: &BoxedObj) -> Option<Val> {
+            let _ = rhs;
+            None
+        }
+    }
+
+    impl std::ops::Add for &Val {
+        type Output = Val;
+
+        fn add(self, rhs: &Val) -> Val {
+            self.value
+                .borrow()
+                .add(&rhs.value.borrow())
+                .unwrap_or_else(|| panic!("unsupported operands for +: {:?} and {:?}", self, rhs))
+        }
+    }
+
+    impl std::ops::Sub for &Val {
+        type Output = Val;
+
+        fn sub(self, rhs: &Val) -> Val {
+            self.value
+                .borrow()
+                .sub(&rhs.value.borrow())
+                .unwrap_or_else(|| panic!("unsupported operands for -: {:?} and {:?}", self, rhs))
+        }
+    }
+
+    impl std::ops::Mul for &Val {
+        type Output = Val;
+
+        fn mul(self, rhs: &Val) -> Val {
+            self.value
+                .borrow()
+                .mul(&rhs.value.borrow())
+                .unwrap_or_else(|| panic!("unsupported operands for *: {:?} and {:?}", self, rhs))
+        }
+    }
+}
+
+// Obj -------------------------------------------------------------------------
+
+trait Obj:
+    AnyBoxedObj
+    + PartialEqBoxedObj
+    + PartialOrdBoxedObj
+    + HashBoxedObj
+    + CloneBoxedObj
+    + ops::BinOp
+    + Any
+    + std::fmt::Debug
+{
+}
+
+// Val -------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Val {
+    value: Rc<RefCell<BoxedObj>>,
+}
+
+impl Eq for Val {}
+
+impl Hash for Val {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.borrow().hash(state)
+    }
+}
+
+impl Val {
+    pub fn new(value: BoxedObj) -> Self {
+        Val {
+            value: Rc::new(RefCell::new(value)),
+        }
+    }
+
+    /// Return reference to object of type T.
+    pub fn object<T: Any>(&self) -> Option<Ref<T>> {
+        let value = self.value.borrow();
+        if value.as_any().is::<T>() {
+            return Some(Ref::map(value, |obj| {
+                obj.as_any().downcast_ref::<T>().unwrap()
+            }));
+        }
+
+        None
+    }
+
+    /// Return mutable reference to object of type T.
+    pub fn object_mut<T: Any>(&self) -> Option<RefMut<T>> {
+        let value = self.value.borrow_mut();
+        if value.as_any().is::<T>() {
+            return Some(RefMut::map(value, |obj| {
+                obj.as_any_mut().downcast_mut::<T>().unwrap()
+            }));
+        }
+
+        None
+    }
+
+    /// Return mutable reference to object of type T.
+    pub fn into_object<T: Any>(self) -> Option<T> {
+        let value = Rc::try_unwrap(self.value).unwrap().into_inner();
+        if let Ok(inner) = value.into_any().downcast::<T>() {
+            return Some(*inner);
+        }
+
+        None
+    }
+
+    /// Return an independent copy of this `Val`, rather than a new handle
+    /// onto the same `Rc`-shared object.
+    pub fn deep_clone(&self) -> Val {
+        Val::new(self.value.borrow().dyn_clone())
+    }
+}
+
+// Obj Int ---------------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone)]
+struct Int {
+    int: i64,
+}
+
+impl Obj for Int {}
+
+impl ops::BinOp for Int {
+    fn add(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int + i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int + c.x, y: c.y }))
+        } else {
+            None
+        }
+    }
+
+    fn sub(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int - i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int - c.x, y: -c.y }))
+        } else {
+            None
+        }
+    }
+
+    fn mul(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(self.int * i.int))
+        } else if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.int * c.x, y: self.int * c.y }))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<i64> for Val {
+    fn from(int: i64) -> Self {
+        Val::new(Box::new(Int { int }))
+    }
+}
+
+// Obj Complex -----------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd, Hash, Clone)]
+struct Complex {
+    x: i64,
+    y: i64,
+}
+
+impl Obj for Complex {}
+
+impl ops::BinOp for Complex {
+    fn add(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.x + c.x, y: self.y + c.y }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x + i.int, y: self.y }))
+        } else {
+            None
+        }
+    }
+
+    fn sub(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            Some(Val::from(Complex { x: self.x - c.x, y: self.y - c.y }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x - i.int, y: self.y }))
+        } else {
+            None
+        }
+    }
+
+    fn mul(&self, rhs: &BoxedObj) -> Option<Val> {
+        if let Some(c) = rhs.as_any().downcast_ref::<Complex>() {
+            // (x1 + y1*i) * (x2 + y2*i) = (x1*x2 - y1*y2) + (x1*y2 + y1*x2)*i
+            Some(Val::from(Complex {
+                x: self.x * c.x - self.y * c.y,
+                y: self.x * c.y + self.y * c.x,
+            }))
+        } else if let Some(i) = rhs.as_any().downcast_ref::<Int>() {
+            Some(Val::from(Complex { x: self.x * i.int, y: self.y * i.int }))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}+{}i", self.x, self.y)
+    }
+}
+
+impl From<Complex> for Val {
+    fn from(value: Complex) -> Self {
+        Val::new(Box::new(value))
+    }
+}
+
+// main ------------------------------------------------------------------------
+
+fn main() {
+    // Create an int
+    let ival = Val::from(1337);
+    println!("ival = {:?}", ival);
+
+    // Create the complex
+    let complex = Complex { x: 23, y: 42 };
+    println!("complex = {:?}", complex);
+
+    // Turn complex into a val
+    let val = Val::from(complex);
+    println!("val = {:?}", val);
+
+    // Use Vals as dictionary keys, the way a scripting runtime would.
+    let mut dict: HashMap<Val, &str> = HashMap::new();
+    dict.insert(Val::from(1337), "lucky number");
+    dict.insert(Val::from(Complex { x: 23, y: 42 }), "a complex one");
+
+    println!("dict[ival] = {:?}", dict.get(&ival));
+    println!("dict[val] = {:?}", dict.get(&val));
+    println!("dict[Val::from(7)] = {:?}", dict.get(&Val::from(7)));
+
+    // `Rc::clone`-alike: a shared handle onto the same object.
+    let shared = Val {
+        value: Rc::clone(&val.value),
+    };
+
+    // `deep_clone`: an independent copy of the object.
+    let copy = val.deep_clone();
+
+    val.object_mut::<Complex>().unwrap().x = 9999;
+    println!("shared sees the mutation = {:?}", shared.object::<Complex>().unwrap());
+    println!("copy does not = {:?}", copy.object::<Complex>().unwrap());
+
+    // Dynamic arithmetic across heterogeneous Obj types.
+    let a = Val::from(Complex { x: 1, y: 2 });
+    let b = Val::from(Complex { x: 3, y: 4 });
+    let i = Val::from(10);
+
+    println!("a + b = {:?}", &a + &b);
+    println!("a - b = {:?}", &a - &b);
+    println!("a * b = {:?}", &a * &b);
+    println!("a + i = {:?}", &a + &i);
+    println!("i * a = {:?}", &i * &a);
+    println!("i + i = {:?}", &i + &i);
+}
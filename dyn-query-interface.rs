@@ -0,0 +1,308 @@
+#![feature(ptr_metadata)]
+
+use std::any::{Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt::Display;
+use std::rc::Rc;
+
+type BoxedObj = Box<dyn Obj>;
+
+// AnyBoxedObj -----------------------------------------------------------------
+
+trait AnyBoxedObj {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T> AnyBoxedObj for T
+where
+    T: 'static + Obj,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+// PartialEqBoxedObj -----------------------------------------------------------
+
+trait PartialEqBoxedObj {
+    fn dyn_eq(&self, other: &BoxedObj) -> bool;
+}
+
+impl<T> PartialEqBoxedObj for T
+where
+    T: 'static + Obj + PartialEq,
+{
+    fn dyn_eq(&self, other: &BoxedObj) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.eq(other)
+        } else {
+            false
+        }
+    }
+}
+
+impl PartialEq for BoxedObj {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+// fix for `move occurs because `*__arg_1_0` has type `Box<dyn Obj>`, which does not implement the `Copy` trait`
+// https://github.com/rust-lang/rust/issues/31740#issuecomment-700950186
+impl PartialEq<&Self> for BoxedObj {
+    fn eq(&self, other: &&Self) -> bool {
+        self.dyn_eq(other)
+    }
+}
+
+// PartialOrdBoxedObj ----------------------------------------------------------
+
+trait PartialOrdBoxedObj {
+    fn dyn_partial_cmp(&self, other: &BoxedObj) -> Option<std::cmp::Ordering>;
+}
+
+impl<T> PartialOrdBoxedObj for T
+where
+    T: 'static + Obj + PartialEq + PartialOrd,
+{
+    fn dyn_partial_cmp(&self, other: &BoxedObj) -> Option<std::cmp::Ordering> {
+        if let Some(other) = other.as_any().downcast_ref::<T>() {
+            self.partial_cmp(other)
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialOrd for BoxedObj {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.dyn_partial_cmp(other)
+    }
+}
+
+// ObjectBoxedObj ----------------------------------------------------------------
+
+/// A type-erased pointer into a registered trait object, carried alongside
+/// the vtable metadata that produced it. `interfaces!` is the only producer
+/// of a `Query`, which is what lets `Val::query_ref`/`query_mut` assume the
+/// metadata always matches the trait whose `TypeId` was asked for.
+struct Query {
+    data: *const (),
+    metadata: *const (),
+}
+
+trait ObjectBoxedObj {
+    /// Look up a trait coercion for `target` registered via `interfaces!`.
+    fn dyn_query(&self, target: TypeId) -> Option<Query>;
+    /// Mutable counterpart of [`ObjectBoxedObj::dyn_query`].
+    fn dyn_query_mut(&mut self, target: TypeId) -> Option<Query>;
+}
+
+/// Registers trait-object coercions for a concrete `Obj` type, so a `Val`
+/// can later recover `&dyn Trait`/`&mut dyn Trait` for any of the listed
+/// traits via [`Val::query_ref`]/[`Val::query_mut`]. This macro is the only
+/// place allowed to build a `Query`, since it is the only place that can
+/// guarantee the `TypeId` queried for and the vtable metadata stashed into
+/// it agree with each other.
+macro_rules! interfaces {
+    ($ty:ty : $($trait:ty),* $(,)?) => {
+        impl ObjectBoxedObj for $ty {
+            fn dyn_query(&self, target: TypeId) -> Option<Query> {
+                $(
+                    if target == TypeId::of::<$trait>() {
+                        let obj: &$trait = self;
+                        let (data, metadata) = (obj as *const $trait).to_raw_parts();
+                        return Some(Query {
+                            data: data as *const (),
+                            // SAFETY: `metadata` was produced from `&$trait`, so
+                            // reinterpreting it as `$trait`'s own metadata type
+                            // further down in `query_ref`/`query_mut` is sound.
+                            metadata: unsafe { std::mem::transmute_copy(&metadata) },
+                        });
+                    }
+                )*
+                let _ = target;
+                None
+            }
+
+            fn dyn_query_mut(&mut self, target: TypeId) -> Option<Query> {
+                $(
+                    if target == TypeId::of::<$trait>() {
+                        let obj: &mut $trait = self;
+                        let (data, metadata) = (obj as *mut $trait).to_raw_parts();
+                        return Some(Query {
+                            data: data as *const (),
+                            metadata: unsafe { std::mem::transmute_copy(&metadata) },
+                        });
+                    }
+                )*
+                let _ = target;
+                None
+            }
+        }
+    };
+}
+
+// Obj -------------------------------------------------------------------------
+
+trait Obj: AnyBoxedObj + PartialEqBoxedObj + PartialOrdBoxedObj + ObjectBoxedObj + Any + std::fmt::Debug {}
+
+// Val -------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Val {
+    value: Rc<RefCell<BoxedObj>>,
+}
+
+impl Val {
+    pub fn new(value: BoxedObj) -> Self {
+        Val {
+            value: Rc::new(RefCell::new(value)),
+        }
+    }
+
+    /// Return reference to object of type T.
+    pub fn object<T: Any>(&self) -> Option<Ref<T>> {
+        let value = self.value.borrow();
+        if value.as_any().is::<T>() {
+            return Some(Ref::map(value, |obj| {
+                obj.as_any().downcast_ref::<T>().unwrap()
+            }));
+        }
+
+        None
+    }
+
+    /// Return mutable reference to object of type T.
+    pub fn object_mut<T: Any>(&self) -> Option<RefMut<T>> {
+        let value = self.value.borrow_mut();
+        if value.as_any().is::<T>() {
+            return Some(RefMut::map(value, |obj| {
+                obj.as_any_mut().downcast_mut::<T>().unwrap()
+            }));
+        }
+
+        None
+    }
+
+    /// Return mutable reference to object of type T.
+    pub fn into_object<T: Any>(self) -> Option<T> {
+        let value = Rc::try_unwrap(self.value).unwrap().into_inner();
+        if let Ok(inner) = value.into_any().downcast::<T>() {
+            return Some(*inner);
+        }
+
+        None
+    }
+
+    /// Ask the inner object whether it implements trait `U` (registered via
+    /// `interfaces!`), returning a `&dyn U` borrowed through the `Val`'s
+    /// `RefCell` if so.
+    pub fn query_ref<U: ?Sized + 'static>(&self) -> Option<Ref<U>> {
+        let value = self.value.borrow();
+        let query = value.dyn_query(TypeId::of::<U>())?;
+        let metadata: <U as std::ptr::Pointee>::Metadata =
+            unsafe { std::mem::transmute_copy(&query.metadata) };
+
+        Some(Ref::map(value, |_| unsafe {
+            &*std::ptr::from_raw_parts::<U>(query.data, metadata)
+        }))
+    }
+
+    /// Mutable counterpart of [`Val::query_ref`].
+    pub fn query_mut<U: ?Sized + 'static>(&self) -> Option<RefMut<U>> {
+        let mut value = self.value.borrow_mut();
+        let query = value.dyn_query_mut(TypeId::of::<U>())?;
+        let metadata: <U as std::ptr::Pointee>::Metadata =
+            unsafe { std::mem::transmute_copy(&query.metadata) };
+
+        Some(RefMut::map(value, |_| unsafe {
+            &mut *std::ptr::from_raw_parts_mut::<U>(query.data as *mut (), metadata)
+        }))
+    }
+}
+
+// Obj Int ---------------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Int {
+    int: i64,
+}
+
+impl Obj for Int {}
+
+interfaces!(Int:);
+
+impl From<i64> for Val {
+    fn from(int: i64) -> Self {
+        Val::new(Box::new(Int { int }))
+    }
+}
+
+// Obj Complex -----------------------------------------------------------------
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Complex {
+    x: i64,
+    y: i64,
+}
+
+impl Obj for Complex {}
+
+interfaces!(Complex: dyn Display);
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}+{}i", self.x, self.y)
+    }
+}
+
+impl From<Complex> for Val {
+    fn from(value: Complex) -> Self {
+        Val::new(Box::new(value))
+    }
+}
+
+// main ------------------------------------------------------------------------
+
+fn main() {
+    // Create an int
+    let ival = Val::from(1337);
+    println!("ival = {:?}", ival);
+
+    // Create the complex
+    let complex = Complex { x: 23, y: 42 };
+    println!("complex = {:?}", complex);
+
+    // Turn complex into a val
+    let val = Val::from(complex);
+    println!("val = {:?}", val);
+
+    // Query the val for a trait it doesn't implement.
+    println!("ival.query_ref::<dyn Display>() = {:?}", ival.query_ref::<dyn Display>().is_some());
+
+    // Query the val for `dyn Display`, registered via `interfaces!`.
+    {
+        let displayable = val.query_ref::<dyn Display>().unwrap();
+        println!("val as dyn Display = {}", displayable);
+    }
+
+    // Mutate through the queried trait object's underlying storage and
+    // observe the change through a plain `object` downcast.
+    {
+        let mut cp = val.object_mut::<Complex>().unwrap();
+        cp.x = 1337;
+        cp.y = 666;
+    }
+
+    let displayable = val.query_mut::<dyn Display>().unwrap();
+    println!("val as dyn Display (mutated) = {}", displayable);
+}